@@ -79,12 +79,11 @@ fn bench_dbtext(c: &mut Criterion) {
         let mut buffer = Vec::with_capacity(200 * 1024 * 1024);
         group.throughput(Throughput::Bytes(buf.len() as u64));
         group.bench_function("compress-only", |b| {
-            b.iter(|| unsafe { compressor.compress_into(&buf, &mut buffer) });
+            b.iter(|| compressor.compress_into(&buf, &mut buffer));
         });
 
-        unsafe {
-            compressor.compress_into(&buf, &mut buffer);
-        };
+        buffer.clear();
+        compressor.compress_into(&buf, &mut buffer);
         let decompressor = compressor.decompressor();
         group.bench_function("decompress", |b| {
             b.iter_with_large_drop(|| decompressor.decompress(&buffer));
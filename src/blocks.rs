@@ -0,0 +1,340 @@
+//! Block-framed layout for O(1) random access to individual records.
+//!
+//! Records are grouped into fixed-count blocks, each independently
+//! decodable, with a block-offset directory at the head of the buffer so a
+//! single record can be recovered by [`BlockDecompressor::get`] without
+//! decoding any other block.
+
+use std::sync::Arc;
+
+use crate::compress::Compressor;
+use crate::decompress::{DecompressError, Decompressor};
+use crate::frame::write_index_and_payload;
+use crate::table::{SymbolTable, TableDecodeError};
+
+/// An error produced while decoding a blob written by [`Compressor::compress_blocks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockDecodeError {
+    /// The byte slice ended before a complete header, directory, or block could be read.
+    Truncated,
+    /// The embedded symbol table failed to decode.
+    Table(TableDecodeError),
+    /// The requested record's coded bytes failed to decode.
+    Decompress(DecompressError),
+    /// The requested record index was not less than the blob's total record count.
+    RecordOutOfRange {
+        /// The index that was requested.
+        index: usize,
+        /// The total number of records in the blob.
+        total_records: usize,
+    },
+    /// The directory was empty, or its first entry didn't start at record
+    /// index 0, despite the blob claiming a nonzero record count.
+    InvalidDirectory,
+}
+
+impl From<TableDecodeError> for BlockDecodeError {
+    fn from(err: TableDecodeError) -> Self {
+        Self::Table(err)
+    }
+}
+
+impl From<DecompressError> for BlockDecodeError {
+    fn from(err: DecompressError) -> Self {
+        Self::Decompress(err)
+    }
+}
+
+impl std::fmt::Display for BlockDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "truncated block container"),
+            Self::Table(err) => write!(f, "invalid embedded symbol table: {err}"),
+            Self::Decompress(err) => write!(f, "invalid coded record: {err}"),
+            Self::RecordOutOfRange {
+                index,
+                total_records,
+            } => write!(f, "record index {index} out of range (have {total_records} records)"),
+            Self::InvalidDirectory => write!(f, "block directory is empty or doesn't start at record index 0"),
+        }
+    }
+}
+
+impl std::error::Error for BlockDecodeError {}
+
+impl Compressor {
+    /// Compresses `records` into a block-framed blob: records are grouped
+    /// into blocks of up to `records_per_block` each, independently
+    /// decodable, with a directory of block offsets and first-record
+    /// indices at the head of the buffer. Tune `records_per_block` to trade
+    /// random-access granularity against per-block framing overhead.
+    pub fn compress_blocks(&self, records: &[&[u8]], records_per_block: usize) -> Vec<u8> {
+        assert!(records_per_block > 0, "records_per_block must be nonzero");
+
+        let table_bytes = self.export_table();
+
+        let mut directory = Vec::new();
+        let mut payload = Vec::new();
+        for (block_index, block) in records.chunks(records_per_block).enumerate() {
+            directory.push((
+                payload.len() as u32,
+                (block_index * records_per_block) as u32,
+            ));
+
+            let coded = self.compress_bulk(block);
+            let coded: Vec<&[u8]> = coded.iter().map(Vec::as_slice).collect();
+            write_index_and_payload(&mut payload, &coded);
+        }
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&(table_bytes.len() as u32).to_le_bytes());
+        out.extend_from_slice(&table_bytes);
+        out.extend_from_slice(&(records.len() as u32).to_le_bytes());
+        out.extend_from_slice(&(records_per_block as u32).to_le_bytes());
+        out.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        for (offset, first_record_index) in &directory {
+            out.extend_from_slice(&offset.to_le_bytes());
+            out.extend_from_slice(&first_record_index.to_le_bytes());
+        }
+        out.extend_from_slice(&payload);
+        out
+    }
+}
+
+/// Decodes blobs written by [`Compressor::compress_blocks`], recovering
+/// individual records by decoding only the block that contains them.
+#[derive(Debug)]
+pub struct BlockDecompressor {
+    decompressor: Decompressor,
+    buffer: Vec<u8>,
+    payload_start: usize,
+    /// `(offset relative to `payload_start`, first record index)` per block, in order.
+    directory: Vec<(u32, u32)>,
+    records_per_block: usize,
+    total_records: usize,
+}
+
+impl BlockDecompressor {
+    /// Parses a blob produced by [`Compressor::compress_blocks`].
+    pub fn open(bytes: &[u8]) -> Result<Self, BlockDecodeError> {
+        let mut pos = 0usize;
+
+        let table_len = read_u32(bytes, &mut pos)? as usize;
+        let table_bytes = bytes
+            .get(pos..pos + table_len)
+            .ok_or(BlockDecodeError::Truncated)?;
+        pos += table_len;
+        let table = SymbolTable::from_bytes(table_bytes)?;
+
+        let total_records = read_u32(bytes, &mut pos)? as usize;
+        let records_per_block = read_u32(bytes, &mut pos)? as usize;
+        let num_blocks = read_u32(bytes, &mut pos)? as usize;
+
+        // `num_blocks` comes straight off the (possibly untrusted) input
+        // before any of its claimed entries are known to exist, so don't
+        // size the allocation from it directly: cap it against how many
+        // 8-byte directory entries could actually fit in what's left.
+        const DIRECTORY_ENTRY_SIZE: usize = 8;
+        let max_possible_blocks = bytes.len().saturating_sub(pos) / DIRECTORY_ENTRY_SIZE;
+        let mut directory = Vec::with_capacity(num_blocks.min(max_possible_blocks));
+        for _ in 0..num_blocks {
+            let offset = read_u32(bytes, &mut pos)?;
+            let first_record_index = read_u32(bytes, &mut pos)?;
+            directory.push((offset, first_record_index));
+        }
+
+        // `BlockDecompressor::get` assumes the directory starts at record
+        // index 0 so its `partition_point(...) - 1` lookup never underflows;
+        // validate that invariant here so malformed bytes produce a clean
+        // error instead of a panic deep inside `get`.
+        if total_records > 0 && directory.first().map(|&(_, first)| first) != Some(0) {
+            return Err(BlockDecodeError::InvalidDirectory);
+        }
+
+        Ok(Self {
+            decompressor: Decompressor::new(Arc::new(table)),
+            buffer: bytes.to_vec(),
+            payload_start: pos,
+            directory,
+            records_per_block,
+            total_records,
+        })
+    }
+
+    /// The total number of records this blob holds.
+    pub fn len(&self) -> usize {
+        self.total_records
+    }
+
+    /// Whether this blob holds no records.
+    pub fn is_empty(&self) -> bool {
+        self.total_records == 0
+    }
+
+    /// The `records_per_block` this blob was written with.
+    pub fn block_size(&self) -> usize {
+        self.records_per_block
+    }
+
+    /// Decodes and returns the single record at `index`, decoding only the
+    /// block that contains it.
+    pub fn get(&self, index: usize) -> Result<Vec<u8>, BlockDecodeError> {
+        if index >= self.total_records {
+            return Err(BlockDecodeError::RecordOutOfRange {
+                index,
+                total_records: self.total_records,
+            });
+        }
+
+        // Last directory entry whose first record index is `<= index`.
+        let block_position = self
+            .directory
+            .partition_point(|&(_, first_record_index)| first_record_index as usize <= index)
+            - 1;
+        let (block_offset, first_record_index) = self.directory[block_position];
+
+        let mut pos = self.payload_start + block_offset as usize;
+        let record_count = read_u32(&self.buffer, &mut pos)? as usize;
+
+        // `record_count` comes straight off the (possibly untrusted)
+        // buffer, so cap it against how many 4-byte lengths could actually
+        // fit in what's left rather than allocating for it directly.
+        const LENGTH_ENTRY_SIZE: usize = 4;
+        let max_possible_records = self.buffer.len().saturating_sub(pos) / LENGTH_ENTRY_SIZE;
+        let mut lengths = Vec::with_capacity(record_count.min(max_possible_records));
+        for _ in 0..record_count {
+            lengths.push(read_u32(&self.buffer, &mut pos)? as usize);
+        }
+
+        // The directory only tells us where a block starts and which
+        // record index it begins at; it's not validated against the
+        // block's own `record_count`, so a malformed/adversarial directory
+        // (even one that starts at record index 0) could otherwise point
+        // `local_index` past the end of `lengths`. Bounds-check instead of
+        // indexing directly so that case is a clean error, not a panic.
+        let local_index = index - first_record_index as usize;
+        let preceding_lengths = lengths.get(..local_index).ok_or(BlockDecodeError::Truncated)?;
+        let record_len = *lengths.get(local_index).ok_or(BlockDecodeError::Truncated)?;
+        let record_start = pos + preceding_lengths.iter().sum::<usize>();
+        let record_end = record_start + record_len;
+        let coded = self
+            .buffer
+            .get(record_start..record_end)
+            .ok_or(BlockDecodeError::Truncated)?;
+
+        self.decompressor.decompress(coded).map_err(Into::into)
+    }
+}
+
+fn read_u32(bytes: &[u8], pos: &mut usize) -> Result<u32, BlockDecodeError> {
+    let slice = bytes
+        .get(*pos..*pos + 4)
+        .ok_or(BlockDecodeError::Truncated)?;
+    *pos += 4;
+    Ok(u32::from_le_bytes(slice.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_recovers_every_record_by_random_access() {
+        let records: Vec<&[u8]> = vec![
+            b"the quick brown fox",
+            b"jumps over",
+            b"the lazy dog",
+            b"repeatedly",
+            b"and again",
+        ];
+        let compressor = Compressor::train(&records);
+        let blob = compressor.compress_blocks(&records, 2);
+        let reader = BlockDecompressor::open(&blob).unwrap();
+
+        assert_eq!(reader.len(), records.len());
+        // Access out of order to exercise random access, not just sequential.
+        for &index in &[3, 0, 4, 1, 2] {
+            assert_eq!(reader.get(index).unwrap(), records[index]);
+        }
+    }
+
+    #[test]
+    fn get_rejects_out_of_range_index() {
+        let records: Vec<&[u8]> = vec![b"one", b"two"];
+        let compressor = Compressor::train(&records);
+        let blob = compressor.compress_blocks(&records, 2);
+        let reader = BlockDecompressor::open(&blob).unwrap();
+
+        assert_eq!(
+            reader.get(records.len()),
+            Err(BlockDecodeError::RecordOutOfRange {
+                index: records.len(),
+                total_records: records.len(),
+            })
+        );
+    }
+
+    #[test]
+    fn open_rejects_a_forged_huge_block_count_instead_of_aborting() {
+        let table_bytes = Compressor::train(&[]).export_table();
+
+        let mut blob = Vec::new();
+        blob.extend_from_slice(&(table_bytes.len() as u32).to_le_bytes());
+        blob.extend_from_slice(&table_bytes);
+        blob.extend_from_slice(&0u32.to_le_bytes()); // total_records
+        blob.extend_from_slice(&1u32.to_le_bytes()); // records_per_block
+        blob.extend_from_slice(&u32::MAX.to_le_bytes()); // num_blocks: forged, no entries follow
+
+        assert_eq!(
+            BlockDecompressor::open(&blob).unwrap_err(),
+            BlockDecodeError::Truncated
+        );
+    }
+
+    #[test]
+    fn open_rejects_a_directory_not_starting_at_record_zero() {
+        let table_bytes = Compressor::train(&[]).export_table();
+
+        let mut blob = Vec::new();
+        blob.extend_from_slice(&(table_bytes.len() as u32).to_le_bytes());
+        blob.extend_from_slice(&table_bytes);
+        blob.extend_from_slice(&5u32.to_le_bytes()); // total_records
+        blob.extend_from_slice(&2u32.to_le_bytes()); // records_per_block
+        blob.extend_from_slice(&1u32.to_le_bytes()); // num_blocks
+        blob.extend_from_slice(&0u32.to_le_bytes()); // block offset
+        blob.extend_from_slice(&3u32.to_le_bytes()); // first_record_index (should be 0)
+
+        assert_eq!(
+            BlockDecompressor::open(&blob).unwrap_err(),
+            BlockDecodeError::InvalidDirectory
+        );
+    }
+
+    #[test]
+    fn get_rejects_a_directory_entry_that_misreports_its_block_contents() {
+        let records: Vec<&[u8]> = vec![b"one", b"two", b"three", b"four", b"five", b"six"];
+        let compressor = Compressor::train(&records);
+        let mut blob = compressor.compress_blocks(&records, 2);
+
+        // Locate the 3 directory entries (8 bytes each: offset, then
+        // first_record_index) right after the header, and swap the 2nd and
+        // 3rd so their claimed `first_record_index`es ([0, 2, 4] -> [0, 4,
+        // 2]) no longer describe the record counts their blocks actually
+        // hold, while the directory still (validly) starts at 0.
+        let mut pos = 0usize;
+        let table_len = read_u32(&blob, &mut pos).unwrap() as usize;
+        pos += table_len;
+        pos += 4; // total_records
+        pos += 4; // records_per_block
+        let num_blocks = read_u32(&blob, &mut pos).unwrap() as usize;
+        assert_eq!(num_blocks, 3);
+        let entry_1_start = pos + 8;
+        let entry_2_start = pos + 16;
+        for i in 0..8 {
+            blob.swap(entry_1_start + i, entry_2_start + i);
+        }
+
+        let reader = BlockDecompressor::open(&blob).unwrap();
+        assert!(reader.get(3).is_err());
+    }
+}
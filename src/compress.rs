@@ -0,0 +1,188 @@
+//! Compression: turning raw byte strings into FSST-coded byte strings.
+
+use std::sync::Arc;
+
+use crate::decompress::Decompressor;
+use crate::symbol::ESCAPE_CODE;
+use crate::table::{SymbolTable, TableDecodeError};
+
+/// A trained FSST symbol table, ready to compress byte strings.
+///
+/// Build one with [`Compressor::train`], then reuse it across every buffer
+/// that shares the same data distribution: compression is cheap, training
+/// is not.
+#[derive(Debug, Clone)]
+pub struct Compressor {
+    table: Arc<SymbolTable>,
+}
+
+impl Compressor {
+    /// Trains a symbol table from representative sample inputs and returns a
+    /// compressor ready to use.
+    pub fn train(samples: &[&[u8]]) -> Self {
+        Self {
+            table: Arc::new(SymbolTable::train(samples)),
+        }
+    }
+
+    /// Trains from at most `sample_count` buffers evenly spaced across
+    /// `samples`, instead of every one of them. A `sample_count` of 0 is
+    /// treated as 1 when `samples` is non-empty.
+    ///
+    /// Training cost is dominated by how much input it scans, so when a
+    /// caller has many chunks that share one data distribution (e.g. every
+    /// chunk of a column), training on a representative sample and reusing
+    /// the resulting `Compressor` across all chunks is far cheaper than
+    /// training fresh per chunk. The key invariant is that one symbol table
+    /// is produced once here and then shared immutably by every chunk that
+    /// compresses against it.
+    pub fn train_on_samples(samples: &[&[u8]], sample_count: usize) -> Self {
+        if samples.len() <= sample_count {
+            return Self::train(samples);
+        }
+
+        let stride = samples.len().div_ceil(sample_count.max(1));
+        let sampled: Vec<&[u8]> = samples.iter().step_by(stride).copied().collect();
+        Self::train(&sampled)
+    }
+
+    /// Compresses `input`, appending the coded bytes to `output`.
+    ///
+    /// For best performance, reserve `output`'s capacity up front: the
+    /// worst case is every input byte becoming a 2-byte escape sequence, so
+    /// `2 * input.len()` spare capacity avoids any reallocation.
+    pub fn compress_into(&self, input: &[u8], output: &mut Vec<u8>) {
+        let mut pos = 0;
+        while pos < input.len() {
+            match self.table.longest_match(&input[pos..]) {
+                Some((code, len)) => {
+                    output.push(code);
+                    pos += len;
+                }
+                None => {
+                    output.push(ESCAPE_CODE);
+                    output.push(input[pos]);
+                    pos += 1;
+                }
+            }
+        }
+    }
+
+    /// Compresses every buffer in `inputs` independently, returning one
+    /// coded buffer per input.
+    pub fn compress_bulk(&self, inputs: &[&[u8]]) -> Vec<Vec<u8>> {
+        inputs
+            .iter()
+            .map(|input| {
+                let mut output = Vec::with_capacity(2 * input.len());
+                self.compress_into(input, &mut output);
+                output
+            })
+            .collect()
+    }
+
+    /// Builds a [`Decompressor`] sharing this compressor's symbol table.
+    pub fn decompressor(&self) -> Decompressor {
+        Decompressor::new(Arc::clone(&self.table))
+    }
+
+    /// Serializes the trained symbol table so it can be persisted (e.g. in
+    /// a columnar store's metadata) and later reloaded with
+    /// [`Compressor::from_table_bytes`] or [`Decompressor::from_table_bytes`],
+    /// skipping the cost of re-training.
+    pub fn export_table(&self) -> Vec<u8> {
+        self.table.to_bytes()
+    }
+
+    /// Rebuilds a compressor from a symbol table previously produced by
+    /// [`Compressor::export_table`], without re-training.
+    pub fn from_table_bytes(bytes: &[u8]) -> Result<Self, TableDecodeError> {
+        Ok(Self {
+            table: Arc::new(SymbolTable::from_bytes(bytes)?),
+        })
+    }
+
+    /// Compresses every buffer in `inputs` in parallel across a rayon
+    /// thread pool, sharing this compressor's already-trained symbol table
+    /// across every worker so the result stays table-compatible with
+    /// `compress_bulk` and every other chunk compressed against the same
+    /// table.
+    #[cfg(feature = "rayon")]
+    pub fn compress_bulk_parallel(&self, inputs: &[&[u8]]) -> Vec<Vec<u8>> {
+        use rayon::prelude::*;
+
+        inputs
+            .par_iter()
+            .map(|input| {
+                let mut output = Vec::with_capacity(2 * input.len());
+                self.compress_into(input, &mut output);
+                output
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_compress_and_decompress() {
+        let samples: Vec<&[u8]> = vec![b"the quick brown fox", b"the slow brown dog"];
+        let compressor = Compressor::train(&samples);
+        let decompressor = compressor.decompressor();
+
+        for input in &samples {
+            let mut coded = Vec::new();
+            compressor.compress_into(input, &mut coded);
+            assert_eq!(decompressor.decompress(&coded).unwrap(), *input);
+        }
+    }
+
+    #[test]
+    fn round_trips_table_through_export_and_reload() {
+        let samples: Vec<&[u8]> = vec![b"the quick brown fox", b"the slow brown dog"];
+        let compressor = Compressor::train(&samples);
+        let table_bytes = compressor.export_table();
+
+        let reloaded = Compressor::from_table_bytes(&table_bytes).unwrap();
+        let mut original_coded = Vec::new();
+        compressor.compress_into(samples[0], &mut original_coded);
+        let mut reloaded_coded = Vec::new();
+        reloaded.compress_into(samples[0], &mut reloaded_coded);
+        assert_eq!(original_coded, reloaded_coded);
+    }
+
+    #[test]
+    fn train_on_samples_produces_a_usable_compressor() {
+        let samples: Vec<&[u8]> = (0..10)
+            .map(|_| b"the quick brown fox jumps over the lazy dog".as_slice())
+            .collect();
+        let compressor = Compressor::train_on_samples(&samples, 3);
+        let decompressor = compressor.decompressor();
+
+        let mut coded = Vec::new();
+        compressor.compress_into(samples[0], &mut coded);
+        assert_eq!(decompressor.decompress(&coded).unwrap(), samples[0]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn compress_bulk_parallel_matches_compress_bulk() {
+        let samples: Vec<&[u8]> = vec![
+            b"the quick brown fox",
+            b"the slow brown dog",
+            b"jumps over the lazy dog",
+        ];
+        let compressor = Compressor::train(&samples);
+
+        let sequential = compressor.compress_bulk(&samples);
+        let parallel = compressor.compress_bulk_parallel(&samples);
+        assert_eq!(sequential, parallel);
+
+        let decompressor = compressor.decompressor();
+        for (coded, input) in parallel.iter().zip(&samples) {
+            assert_eq!(decompressor.decompress(coded).unwrap(), *input);
+        }
+    }
+}
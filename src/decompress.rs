@@ -0,0 +1,97 @@
+//! Decompression: turning FSST-coded byte strings back into raw bytes.
+
+use std::sync::Arc;
+
+use crate::symbol::ESCAPE_CODE;
+use crate::table::{SymbolTable, TableDecodeError};
+
+/// Decodes buffers produced by a [`crate::Compressor`] sharing the same
+/// symbol table.
+#[derive(Debug, Clone)]
+pub struct Decompressor {
+    table: Arc<SymbolTable>,
+}
+
+/// An error produced while decoding coded bytes that don't match what this
+/// decompressor's symbol table would have produced, e.g. truncated or
+/// otherwise corrupted input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecompressError {
+    /// The coded bytes ended mid-code: an escape code with no literal byte
+    /// following it.
+    Truncated,
+    /// A code didn't address any entry in the symbol table.
+    InvalidCode(u8),
+}
+
+impl std::fmt::Display for DecompressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "truncated coded input"),
+            Self::InvalidCode(code) => write!(f, "code {code} has no matching symbol"),
+        }
+    }
+}
+
+impl std::error::Error for DecompressError {}
+
+impl Decompressor {
+    pub(crate) fn new(table: Arc<SymbolTable>) -> Self {
+        Self { table }
+    }
+
+    /// Decodes a single buffer produced by `Compressor::compress_into` or
+    /// `Compressor::compress_bulk`.
+    ///
+    /// Returns an error instead of panicking if `input` is truncated or
+    /// otherwise doesn't correspond to this table, since coded bytes handed
+    /// to a decompressor (e.g. reloaded from disk or off the network) can't
+    /// be assumed well-formed.
+    pub fn decompress(&self, input: &[u8]) -> Result<Vec<u8>, DecompressError> {
+        let mut output = Vec::with_capacity(input.len() * 2);
+        let mut pos = 0;
+        while pos < input.len() {
+            let code = input[pos];
+            pos += 1;
+            if code == ESCAPE_CODE {
+                let literal = *input.get(pos).ok_or(DecompressError::Truncated)?;
+                output.push(literal);
+                pos += 1;
+            } else {
+                let symbol = self
+                    .table
+                    .symbols()
+                    .get(code as usize)
+                    .ok_or(DecompressError::InvalidCode(code))?;
+                output.extend_from_slice(symbol.as_bytes());
+            }
+        }
+        Ok(output)
+    }
+
+    /// Rebuilds a decompressor from a symbol table previously produced by
+    /// [`crate::Compressor::export_table`], with no compressor required.
+    pub fn from_table_bytes(bytes: &[u8]) -> Result<Self, TableDecodeError> {
+        Ok(Self {
+            table: Arc::new(SymbolTable::from_bytes(bytes)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compress::Compressor;
+
+    #[test]
+    fn decompresses_bytes_coded_by_a_decompressor_reloaded_from_table_bytes() {
+        let samples: Vec<&[u8]> = vec![b"the quick brown fox", b"the slow brown dog"];
+        let compressor = Compressor::train(&samples);
+        let table_bytes = compressor.export_table();
+
+        let mut coded = Vec::new();
+        compressor.compress_into(samples[0], &mut coded);
+
+        let decompressor = super::Decompressor::from_table_bytes(&table_bytes).unwrap();
+        assert_eq!(decompressor.decompress(&coded).unwrap(), samples[0]);
+    }
+}
@@ -0,0 +1,196 @@
+//! A self-describing compressed frame: a blob that carries its own symbol
+//! table (or falls back to storing raw bytes) so it can be persisted and
+//! later handed to [`decompress_frame`] with no out-of-band state.
+
+use std::sync::Arc;
+
+use crate::compress::Compressor;
+use crate::decompress::{DecompressError, Decompressor};
+use crate::table::{SymbolTable, TableDecodeError};
+
+/// How the payload of a blob written by [`Compressor::compress_frame`] was encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum CompressionMethod {
+    /// Payload is FSST-coded strings, preceded by their symbol table.
+    Fsst = 0,
+    /// Payload is the original bytes, stored verbatim.
+    Stored = 1,
+}
+
+impl CompressionMethod {
+    fn from_tag(tag: u8) -> Result<Self, FrameDecodeError> {
+        match tag {
+            0 => Ok(Self::Fsst),
+            1 => Ok(Self::Stored),
+            other => Err(FrameDecodeError::UnknownMethod(other)),
+        }
+    }
+}
+
+/// An error produced while decoding a frame written by [`Compressor::compress_frame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameDecodeError {
+    /// The byte slice ended before a complete frame could be read.
+    Truncated,
+    /// The leading method tag byte wasn't `Fsst` (0) or `Stored` (1).
+    UnknownMethod(u8),
+    /// The embedded symbol table failed to decode.
+    Table(TableDecodeError),
+    /// A string's coded bytes failed to decode.
+    Decompress(DecompressError),
+}
+
+impl From<TableDecodeError> for FrameDecodeError {
+    fn from(err: TableDecodeError) -> Self {
+        Self::Table(err)
+    }
+}
+
+impl From<DecompressError> for FrameDecodeError {
+    fn from(err: DecompressError) -> Self {
+        Self::Decompress(err)
+    }
+}
+
+impl std::fmt::Display for FrameDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "truncated frame"),
+            Self::UnknownMethod(tag) => write!(f, "unknown frame method tag {tag}"),
+            Self::Table(err) => write!(f, "invalid embedded symbol table: {err}"),
+            Self::Decompress(err) => write!(f, "invalid coded string: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for FrameDecodeError {}
+
+impl Compressor {
+    /// Compresses `strings` into a single self-describing blob: a method
+    /// tag, the symbol table (if used), a per-string length index, then the
+    /// payload.
+    ///
+    /// If FSST doesn't shrink this particular input once the embedded
+    /// symbol table and indexes are accounted for, the frame transparently
+    /// falls back to storing the bytes verbatim under the `Stored` tag, so
+    /// the frame is never larger than that verbatim encoding (the original
+    /// bytes plus the tag and length index) of the same strings.
+    pub fn compress_frame(&self, strings: &[&[u8]]) -> Vec<u8> {
+        let coded = self.compress_bulk(strings);
+        let coded: Vec<&[u8]> = coded.iter().map(Vec::as_slice).collect();
+
+        let mut fsst_frame = vec![CompressionMethod::Fsst as u8];
+        let table_bytes = self.export_table();
+        fsst_frame.extend_from_slice(&(table_bytes.len() as u32).to_le_bytes());
+        fsst_frame.extend_from_slice(&table_bytes);
+        write_index_and_payload(&mut fsst_frame, &coded);
+
+        let mut stored_frame = vec![CompressionMethod::Stored as u8];
+        write_index_and_payload(&mut stored_frame, strings);
+
+        // Compare the *total* size of each candidate frame (tag + table +
+        // index + payload), not just the coded payload against the raw
+        // input: a small input can still inflate once its symbol table is
+        // embedded, even though the coded bytes themselves shrank.
+        if fsst_frame.len() < stored_frame.len() {
+            fsst_frame
+        } else {
+            stored_frame
+        }
+    }
+}
+
+/// Appends a `u32` count, a `u32` length per part, then the parts
+/// themselves, to `out`. Shared by every container format in this crate
+/// that needs to slice a payload back into its original pieces.
+pub(crate) fn write_index_and_payload(out: &mut Vec<u8>, parts: &[&[u8]]) {
+    out.extend_from_slice(&(parts.len() as u32).to_le_bytes());
+    for part in parts {
+        out.extend_from_slice(&(part.len() as u32).to_le_bytes());
+    }
+    for part in parts {
+        out.extend_from_slice(part);
+    }
+}
+
+/// Decodes a blob produced by [`Compressor::compress_frame`], recovering
+/// the original strings with no out-of-band symbol table required.
+pub fn decompress_frame(bytes: &[u8]) -> Result<Vec<Vec<u8>>, FrameDecodeError> {
+    let mut pos = 0usize;
+    let tag = *bytes.first().ok_or(FrameDecodeError::Truncated)?;
+    pos += 1;
+    let method = CompressionMethod::from_tag(tag)?;
+
+    let decompressor = match method {
+        CompressionMethod::Fsst => {
+            let table_len = read_u32(bytes, &mut pos)? as usize;
+            let table_bytes = bytes
+                .get(pos..pos + table_len)
+                .ok_or(FrameDecodeError::Truncated)?;
+            pos += table_len;
+            Some(Decompressor::new(Arc::new(SymbolTable::from_bytes(
+                table_bytes,
+            )?)))
+        }
+        CompressionMethod::Stored => None,
+    };
+
+    let count = read_u32(bytes, &mut pos)? as usize;
+    let mut lengths = Vec::with_capacity(count);
+    for _ in 0..count {
+        lengths.push(read_u32(bytes, &mut pos)? as usize);
+    }
+
+    let mut strings = Vec::with_capacity(count);
+    for len in lengths {
+        let part = bytes
+            .get(pos..pos + len)
+            .ok_or(FrameDecodeError::Truncated)?;
+        pos += len;
+        strings.push(match &decompressor {
+            Some(decompressor) => decompressor.decompress(part)?,
+            None => part.to_vec(),
+        });
+    }
+
+    Ok(strings)
+}
+
+fn read_u32(bytes: &[u8], pos: &mut usize) -> Result<u32, FrameDecodeError> {
+    let slice = bytes
+        .get(*pos..*pos + 4)
+        .ok_or(FrameDecodeError::Truncated)?;
+    *pos += 4;
+    Ok(u32::from_le_bytes(slice.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_fsst_frame() {
+        let strings: Vec<&[u8]> = vec![b"the quick brown fox", b"the slow brown dog"];
+        let compressor = Compressor::train(&strings);
+
+        let frame = compressor.compress_frame(&strings);
+        let decoded = decompress_frame(&frame).unwrap();
+        assert_eq!(decoded, strings.iter().map(|s| s.to_vec()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn falls_back_to_stored_for_pathological_input() {
+        // A tiny, low-repetition input that an embedded symbol table would
+        // inflate rather than shrink, so the frame should pick the `Stored`
+        // method.
+        let strings: Vec<&[u8]> = vec![b"ab", b"cd"];
+        let compressor = Compressor::train(&strings);
+
+        let frame = compressor.compress_frame(&strings);
+        assert_eq!(frame[0], CompressionMethod::Stored as u8);
+
+        let decoded = decompress_frame(&frame).unwrap();
+        assert_eq!(decoded, strings.iter().map(|s| s.to_vec()).collect::<Vec<_>>());
+    }
+}
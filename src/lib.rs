@@ -0,0 +1,21 @@
+//! # fsst
+//!
+//! A Rust implementation of FSST (Fast Static Symbol Table), a light-weight
+//! string compression scheme tuned for fast encoding/decoding of short
+//! strings, as described in the [FSST paper](https://www.vldb.org/pvldb/vol13/p2649-boncz.pdf).
+
+mod blocks;
+mod compress;
+mod decompress;
+mod frame;
+mod stream;
+mod symbol;
+mod table;
+
+pub use blocks::{BlockDecodeError, BlockDecompressor};
+pub use compress::Compressor;
+pub use decompress::{DecompressError, Decompressor};
+pub use frame::{decompress_frame, FrameDecodeError};
+pub use stream::{FsstReader, FsstWriter};
+pub use symbol::{MAX_SYMBOLS, MAX_SYMBOL_LENGTH};
+pub use table::TableDecodeError;
@@ -0,0 +1,154 @@
+//! Streaming `Read`/`Write` adapters so arbitrarily large input can be
+//! piped through FSST without holding the whole buffer in memory at once.
+
+use std::io::{self, Read, Write};
+
+use crate::compress::Compressor;
+use crate::decompress::Decompressor;
+
+/// Bytes buffered internally before a chunk is compressed and flushed.
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Wraps a writer, buffering bytes written through it and periodically
+/// compressing them into length-prefixed frames (a little-endian `u32`
+/// length, then the coded bytes) written to the inner writer.
+pub struct FsstWriter<W: Write> {
+    compressor: Compressor,
+    inner: W,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> FsstWriter<W> {
+    /// Wraps `inner`, compressing everything written through `self` with a
+    /// pre-trained `compressor`.
+    pub fn new(compressor: Compressor, inner: W) -> Self {
+        Self {
+            compressor,
+            inner,
+            buffer: Vec::with_capacity(DEFAULT_CHUNK_SIZE),
+        }
+    }
+
+    fn flush_chunk(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        let mut coded = Vec::with_capacity(2 * self.buffer.len());
+        self.compressor.compress_into(&self.buffer, &mut coded);
+        self.buffer.clear();
+
+        self.inner.write_all(&(coded.len() as u32).to_le_bytes())?;
+        self.inner.write_all(&coded)
+    }
+
+    /// Flushes any buffered bytes as a final frame and returns the
+    /// underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush_chunk()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for FsstWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= DEFAULT_CHUNK_SIZE {
+            self.flush_chunk()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_chunk()?;
+        self.inner.flush()
+    }
+}
+
+/// Reads length-prefixed frames written by a matching [`FsstWriter`] and
+/// transparently decodes them, so compressed input can be consumed through
+/// a plain [`Read`] without holding it all in memory.
+pub struct FsstReader<R: Read> {
+    decompressor: Decompressor,
+    inner: R,
+    pending: Vec<u8>,
+    pending_pos: usize,
+}
+
+impl<R: Read> FsstReader<R> {
+    /// Wraps `inner`, decoding frames using `decompressor`.
+    pub fn new(decompressor: Decompressor, inner: R) -> Self {
+        Self {
+            decompressor,
+            inner,
+            pending: Vec::new(),
+            pending_pos: 0,
+        }
+    }
+
+    /// Reads and decodes the next frame, returning `false` at end of stream.
+    fn fill_pending(&mut self) -> io::Result<bool> {
+        let mut len_buf = [0u8; 4];
+        match self.inner.read_exact(&mut len_buf) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+            Err(err) => return Err(err),
+        }
+
+        // The length prefix comes from the (possibly untrusted) inner
+        // reader, so grow the buffer incrementally via `take` instead of
+        // eagerly allocating `len` bytes: a forged multi-gigabyte length
+        // can't force a huge allocation from just a handful of bytes.
+        let len = u32::from_le_bytes(len_buf) as u64;
+        let mut coded = Vec::new();
+        (&mut self.inner).take(len).read_to_end(&mut coded)?;
+        if coded.len() as u64 != len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "truncated fsst frame",
+            ));
+        }
+
+        self.pending = self
+            .decompressor
+            .decompress(&coded)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        self.pending_pos = 0;
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for FsstReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pending_pos >= self.pending.len() && !self.fill_pending()? {
+            return Ok(0);
+        }
+
+        let available = &self.pending[self.pending_pos..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.pending_pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compress::Compressor;
+
+    #[test]
+    fn round_trips_through_writer_and_reader() {
+        let input = b"the quick brown fox jumps over the lazy dog, repeatedly";
+        let compressor = Compressor::train(&[input]);
+
+        let mut writer = FsstWriter::new(compressor.clone(), Vec::new());
+        writer.write_all(input).unwrap();
+        let coded = writer.finish().unwrap();
+
+        let mut reader = FsstReader::new(compressor.decompressor(), coded.as_slice());
+        let mut decoded = Vec::new();
+        reader.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, input);
+    }
+}
@@ -0,0 +1,49 @@
+//! The `Symbol` type: a short run of bytes addressed by a single code.
+
+/// Maximum length in bytes of a single FSST symbol.
+pub const MAX_SYMBOL_LENGTH: usize = 8;
+
+/// Maximum number of symbols a table may hold.
+///
+/// Codes `0..MAX_SYMBOLS` address table entries; code [`ESCAPE_CODE`] is
+/// reserved to mean "the following byte is a literal, unmatched byte".
+pub const MAX_SYMBOLS: usize = 255;
+
+/// The code that precedes a literal byte which didn't match any symbol.
+pub const ESCAPE_CODE: u8 = 255;
+
+/// A single entry in a symbol table: between 1 and [`MAX_SYMBOL_LENGTH`] bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    bytes: [u8; MAX_SYMBOL_LENGTH],
+    len: u8,
+}
+
+impl Symbol {
+    /// Builds a symbol from a byte slice of length `1..=MAX_SYMBOL_LENGTH`.
+    pub fn new(slice: &[u8]) -> Self {
+        debug_assert!(!slice.is_empty() && slice.len() <= MAX_SYMBOL_LENGTH);
+        let mut bytes = [0u8; MAX_SYMBOL_LENGTH];
+        bytes[..slice.len()].copy_from_slice(slice);
+        Self {
+            bytes,
+            len: slice.len() as u8,
+        }
+    }
+
+    /// The symbol's bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+
+    /// The symbol's length in bytes.
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Whether the symbol is empty (never true for a symbol produced by [`Symbol::new`]).
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
@@ -0,0 +1,180 @@
+//! Symbol table construction (training) shared by [`crate::Compressor`] and
+//! [`crate::Decompressor`].
+
+use std::collections::HashMap;
+
+use crate::symbol::{Symbol, MAX_SYMBOLS, MAX_SYMBOL_LENGTH};
+
+/// Upper bound, in bytes, on how much input [`SymbolTable::train`] inspects
+/// when counting candidate symbols. Training scans substrings of every
+/// length up to `MAX_SYMBOL_LENGTH` at every position, so this keeps
+/// training tractable on very large inputs.
+const TRAINING_SAMPLE_LIMIT: usize = 1 << 20;
+
+/// The serialized table format version written by [`SymbolTable::to_bytes`]
+/// and understood by [`SymbolTable::from_bytes`].
+const TABLE_FORMAT_VERSION: u8 = 1;
+
+/// An error produced while decoding a serialized symbol table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableDecodeError {
+    /// The byte slice ended before a complete table could be read.
+    Truncated,
+    /// The table was encoded with a format version this build doesn't understand.
+    UnsupportedVersion(u8),
+    /// A symbol's declared length was zero or exceeded `MAX_SYMBOL_LENGTH`.
+    InvalidSymbolLength(u8),
+}
+
+impl std::fmt::Display for TableDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "truncated symbol table"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported symbol table version {version}")
+            }
+            Self::InvalidSymbolLength(len) => write!(f, "invalid symbol length {len}"),
+        }
+    }
+}
+
+impl std::error::Error for TableDecodeError {}
+
+/// A trained FSST symbol table: an ordered list of symbols addressed by
+/// their position (their "code").
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+    codes: HashMap<Symbol, u8>,
+}
+
+impl SymbolTable {
+    /// Trains a symbol table from a set of representative input buffers.
+    ///
+    /// Training counts the frequency of every byte run of length
+    /// `1..=MAX_SYMBOL_LENGTH` found in (a bounded sample of) `samples`,
+    /// then greedily keeps the up-to-[`MAX_SYMBOLS`] runs with the highest
+    /// estimated savings (`frequency * length`).
+    pub fn train(samples: &[&[u8]]) -> Self {
+        let counts = Self::count_candidates(samples);
+
+        let mut candidates: Vec<(Vec<u8>, u64)> = counts
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .collect();
+        candidates.sort_unstable_by(|(bytes_a, count_a), (bytes_b, count_b)| {
+            let score_a = *count_a * bytes_a.len() as u64;
+            let score_b = *count_b * bytes_b.len() as u64;
+            score_b.cmp(&score_a).then_with(|| bytes_b.cmp(bytes_a))
+        });
+
+        let symbols = candidates
+            .into_iter()
+            .take(MAX_SYMBOLS)
+            .map(|(bytes, _)| Symbol::new(&bytes))
+            .collect();
+
+        Self::from_symbols(symbols)
+    }
+
+    /// Builds a table directly from an already-decided symbol list, e.g.
+    /// after deserializing one (see `SymbolTable::from_bytes`).
+    pub(crate) fn from_symbols(symbols: Vec<Symbol>) -> Self {
+        let codes = symbols
+            .iter()
+            .enumerate()
+            .map(|(code, symbol)| (*symbol, code as u8))
+            .collect();
+        Self { symbols, codes }
+    }
+
+    fn count_candidates(samples: &[&[u8]]) -> HashMap<Vec<u8>, u64> {
+        let mut counts = HashMap::new();
+        let mut budget = TRAINING_SAMPLE_LIMIT;
+
+        for &sample in samples {
+            let sample = &sample[..sample.len().min(budget)];
+            budget -= sample.len();
+
+            for start in 0..sample.len() {
+                let max_len = MAX_SYMBOL_LENGTH.min(sample.len() - start);
+                for len in 1..=max_len {
+                    *counts
+                        .entry(sample[start..start + len].to_vec())
+                        .or_insert(0) += 1;
+                }
+            }
+
+            if budget == 0 {
+                break;
+            }
+        }
+
+        counts
+    }
+
+    pub(crate) fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Finds the longest symbol matching a prefix of `input`, returning its
+    /// code and length, or `None` if no symbol matches (the caller should
+    /// then escape the next byte).
+    pub(crate) fn longest_match(&self, input: &[u8]) -> Option<(u8, usize)> {
+        let max_len = MAX_SYMBOL_LENGTH.min(input.len());
+        for len in (1..=max_len).rev() {
+            if let Some(&code) = self.codes.get(&Symbol::new(&input[..len])) {
+                return Some((code, len));
+            }
+        }
+        None
+    }
+
+    /// Serializes the table as: a 1-byte format version, a 1-byte symbol
+    /// count, then for each symbol a length byte (`1..=MAX_SYMBOL_LENGTH`)
+    /// followed by its bytes. Codes are implied by table order, and the
+    /// escape code convention (code [`crate::symbol::ESCAPE_CODE`], never a
+    /// table entry) is preserved on reload.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(
+            2 + self.symbols.iter().map(|symbol| 1 + symbol.len()).sum::<usize>(),
+        );
+        out.push(TABLE_FORMAT_VERSION);
+        out.push(self.symbols.len() as u8);
+        for symbol in &self.symbols {
+            out.push(symbol.len() as u8);
+            out.extend_from_slice(symbol.as_bytes());
+        }
+        out
+    }
+
+    /// Reconstructs a table previously serialized with [`SymbolTable::to_bytes`].
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, TableDecodeError> {
+        let mut pos = 0usize;
+
+        let version = *bytes.first().ok_or(TableDecodeError::Truncated)?;
+        pos += 1;
+        if version != TABLE_FORMAT_VERSION {
+            return Err(TableDecodeError::UnsupportedVersion(version));
+        }
+
+        let count = *bytes.get(pos).ok_or(TableDecodeError::Truncated)? as usize;
+        pos += 1;
+
+        let mut symbols = Vec::with_capacity(count);
+        for _ in 0..count {
+            let len = *bytes.get(pos).ok_or(TableDecodeError::Truncated)?;
+            pos += 1;
+            if len == 0 || len as usize > MAX_SYMBOL_LENGTH {
+                return Err(TableDecodeError::InvalidSymbolLength(len));
+            }
+            let symbol_bytes = bytes
+                .get(pos..pos + len as usize)
+                .ok_or(TableDecodeError::Truncated)?;
+            symbols.push(Symbol::new(symbol_bytes));
+            pos += len as usize;
+        }
+
+        Ok(Self::from_symbols(symbols))
+    }
+}